@@ -1,33 +0,0 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::OnceLock;
-use tokio::sync::Mutex;
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct AnalysisRequest {
-    pub code: String,
-    pub options: HashMap<String, String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct AnalysisResponse {
-    pub diagnostics: Vec<String>,
-    pub suggestions: Vec<String>,
-    pub metadata: HashMap<String, String>,
-}
-
-static CONFIG: OnceLock<Mutex<String>> = OnceLock::new();
-
-pub fn set_config(config: String) -> Result<(), String> {
-    let _ = CONFIG.set(Mutex::new(config));
-    Ok(())
-}
-
-pub async fn analyze_code(request: AnalysisRequest) -> Result<AnalysisResponse, String> {
-    // TODO: Implement actual analysis logic
-    Ok(AnalysisResponse {
-        diagnostics: vec![],
-        suggestions: vec![],
-        metadata: HashMap::new(),
-    })
-}
@@ -1,43 +0,0 @@
-use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct AnalysisResult {
-    pub diagnostics: Vec<Diagnostic>,
-    pub symbols: HashMap<String, SymbolInfo>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SymbolInfo {
-    pub name: String,
-    pub kind: SymbolKind,
-    pub location: Location,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub enum SymbolKind {
-    Function,
-    Struct,
-    Enum,
-    Trait,
-    Module,
-    Type,
-    Const,
-    Static,
-    Macro,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Location {
-    pub uri: String,
-    pub range: Range,
-}
-
-pub fn analyze_code(code: &str) -> AnalysisResult {
-    // TODO: Implement actual analysis
-    AnalysisResult {
-        diagnostics: vec![],
-        symbols: HashMap::new(),
-    }
-}
@@ -2,7 +2,7 @@ use rust_analyzer_bridge::analysis::AnalysisRequest;
 use std::io::{self, Read};
 use serde_json::{from_str, to_string};
 use std::fs;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(name = "rust-analyzer-bridge")]
@@ -10,36 +10,80 @@ use clap::Parser;
 struct Cli {
     #[arg(long)]
     config: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Analyze one request read from stdin and exit (the default).
+    Analyze,
+    /// Read one request from stdin, apply every machine-applicable fix
+    /// rustc's own diagnostics suggest, and print the fixed source.
+    Fix,
+    /// Keep a warm rust-analyzer session resident and serve requests over a
+    /// local socket.
+    Serve {
+        /// Unix socket path to listen on. Takes precedence over `--port`.
+        #[arg(long)]
+        socket: Option<String>,
+        /// TCP port to listen on (127.0.0.1).
+        #[arg(long, default_value_t = 7878)]
+        port: u16,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Parse CLI args
     let args = Cli::parse();
 
-    // Load config if provided
     if let Some(config_path) = args.config {
         let config = fs::read_to_string(config_path)?;
         rust_analyzer_bridge::analysis::set_config(config)?;
     }
 
-    // Read input from stdin
+    match args.command.unwrap_or(Commands::Analyze) {
+        Commands::Analyze => run_single_analysis().await,
+        Commands::Fix => run_single_fix().await,
+        Commands::Serve { socket, port } => match socket {
+            Some(path) => Ok(rust_analyzer_bridge::daemon::serve_unix(&path).await?),
+            None => Ok(rust_analyzer_bridge::daemon::serve_tcp(&format!("127.0.0.1:{}", port)).await?),
+        },
+    }
+}
+
+/// Reads one request from stdin, analyzes it, prints the response, and
+/// exits — the original one-shot behavior.
+async fn run_single_analysis() -> Result<(), Box<dyn std::error::Error>> {
     let mut buffer = String::new();
     io::stdin().read_to_string(&mut buffer)?;
 
-    // Parse the request
     let request: AnalysisRequest = from_str(&buffer)?;
 
-    // Analyze the code
     let response = match rust_analyzer_bridge::analysis::analyze_code(request).await {
         Ok(result) => result,
-        Err(err) => {
-            // Return error in a structured format
-            return Err(err.into());
-        }
+        Err(err) => return Err(err.into()),
+    };
+
+    println!("{}", to_string(&response)?);
+
+    Ok(())
+}
+
+/// Reads one request from stdin, applies every machine-applicable fix
+/// rustc's own diagnostics suggest, prints the result, and exits.
+async fn run_single_fix() -> Result<(), Box<dyn std::error::Error>> {
+    let mut buffer = String::new();
+    io::stdin().read_to_string(&mut buffer)?;
+
+    let request: AnalysisRequest = from_str(&buffer)?;
+
+    let response = match rust_analyzer_bridge::analysis::apply_fixes(request).await {
+        Ok(result) => result,
+        Err(err) => return Err(err.into()),
     };
 
-    // Return the response as JSON
     println!("{}", to_string(&response)?);
 
     Ok(())
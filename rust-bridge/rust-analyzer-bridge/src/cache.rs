@@ -0,0 +1,216 @@
+//! Content-addressed caching of [`AnalysisResult`]s.
+//!
+//! Spawning (or round-tripping to) rust-analyzer per request is expensive,
+//! and the same snippet is often re-analyzed (e.g. while an editor debounces
+//! keystrokes). Results are cached under a digest of the code, the relevant
+//! [`RustAnalyzerConfig`] fields, and the rust-analyzer version string, so a
+//! toolchain upgrade naturally invalidates anything cached under the old
+//! version.
+
+use crate::{AnalysisResult, RustAnalyzerConfig};
+use lru::LruCache;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Computes the content-addressed cache key for a piece of code opened under
+/// `file_path`.
+///
+/// Includes `file_path` because `RustAnalyzer::analyze_code` bakes it into
+/// every `SymbolInfo.location.uri` it returns — without it, analyzing the
+/// same code under two different paths would hit the same cache entry and
+/// come back with the first path's URIs. Also includes
+/// `config.executable_path` and `config.working_dir` (analysis can depend on
+/// the working directory's `Cargo.toml`/`rust-toolchain.toml`) and
+/// `tool_version` (rust-analyzer's own `--version` output), so upgrading the
+/// toolchain invalidates entries cached under the old one.
+pub fn cache_key(file_path: &str, code: &str, config: &RustAnalyzerConfig, tool_version: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    file_path.hash(&mut hasher);
+    code.hash(&mut hasher);
+    config.executable_path.hash(&mut hasher);
+    config.working_dir.hash(&mut hasher);
+    tool_version.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// A pluggable store for analysis results, keyed by [`cache_key`].
+pub trait AnalysisCache: Send + Sync {
+    fn get(&self, key: &str) -> Option<AnalysisResult>;
+    fn put(&self, key: &str, result: &AnalysisResult);
+    /// Drops every cached entry.
+    fn clear(&self);
+    /// Drops a single cached entry, if present.
+    fn invalidate(&self, key: &str);
+}
+
+/// Default cache backend: an in-memory LRU keyed by the content-addressed
+/// digest.
+pub struct InMemoryCache {
+    entries: Mutex<LruCache<String, AnalysisResult>>,
+}
+
+impl InMemoryCache {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(1).unwrap());
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+}
+
+impl AnalysisCache for InMemoryCache {
+    fn get(&self, key: &str) -> Option<AnalysisResult> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    fn put(&self, key: &str, result: &AnalysisResult) {
+        self.entries.lock().unwrap().put(key.to_string(), result.clone());
+    }
+
+    fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn invalidate(&self, key: &str) {
+        self.entries.lock().unwrap().pop(key);
+    }
+}
+
+/// On-disk cache backend: each entry is a JSON file named by its hex digest
+/// inside `dir`.
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+impl DiskCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(key)
+    }
+}
+
+impl AnalysisCache for DiskCache {
+    fn get(&self, key: &str) -> Option<AnalysisResult> {
+        let contents = fs::read_to_string(self.entry_path(key)).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    fn put(&self, key: &str, result: &AnalysisResult) {
+        if fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        if let Ok(json) = serde_json::to_string(result) {
+            let _ = fs::write(self.entry_path(key), json);
+        }
+    }
+
+    fn clear(&self) {
+        if let Ok(entries) = fs::read_dir(&self.dir) {
+            for entry in entries.flatten() {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    fn invalidate(&self, key: &str) {
+        let _ = fs::remove_file(self.entry_path(key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_result(explanation: &str) -> AnalysisResult {
+        AnalysisResult {
+            diagnostics: Vec::new(),
+            suggestions: Vec::new(),
+            symbols: Vec::new(),
+            explanation: Some(explanation.to_string()),
+        }
+    }
+
+    #[test]
+    fn cache_key_differs_by_file_path() {
+        let config = RustAnalyzerConfig::default();
+        let a = cache_key("a.rs", "fn main() {}", &config, "1.0.0");
+        let b = cache_key("b.rs", "fn main() {}", &config, "1.0.0");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn cache_key_differs_by_tool_version() {
+        let config = RustAnalyzerConfig::default();
+        let a = cache_key("a.rs", "fn main() {}", &config, "1.0.0");
+        let b = cache_key("a.rs", "fn main() {}", &config, "2.0.0");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn cache_key_is_deterministic() {
+        let config = RustAnalyzerConfig::default();
+        let a = cache_key("a.rs", "fn main() {}", &config, "1.0.0");
+        let b = cache_key("a.rs", "fn main() {}", &config, "1.0.0");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn in_memory_cache_roundtrips_and_invalidates() {
+        let cache = InMemoryCache::new(2);
+        assert!(cache.get("k").is_none());
+
+        cache.put("k", &sample_result("hit"));
+        assert_eq!(cache.get("k").unwrap().explanation, Some("hit".to_string()));
+
+        cache.invalidate("k");
+        assert!(cache.get("k").is_none());
+    }
+
+    #[test]
+    fn in_memory_cache_evicts_least_recently_used() {
+        let cache = InMemoryCache::new(1);
+        cache.put("k1", &sample_result("first"));
+        cache.put("k2", &sample_result("second"));
+
+        assert!(cache.get("k1").is_none());
+        assert_eq!(cache.get("k2").unwrap().explanation, Some("second".to_string()));
+    }
+
+    #[test]
+    fn in_memory_cache_clear_drops_everything() {
+        let cache = InMemoryCache::new(4);
+        cache.put("k1", &sample_result("first"));
+        cache.put("k2", &sample_result("second"));
+
+        cache.clear();
+
+        assert!(cache.get("k1").is_none());
+        assert!(cache.get("k2").is_none());
+    }
+
+    #[test]
+    fn disk_cache_roundtrips_and_invalidates() {
+        let dir = std::env::temp_dir().join(format!(
+            "rust-analyzer-bridge-cache-test-{}",
+            std::process::id()
+        ));
+        let cache = DiskCache::new(dir.clone());
+
+        assert!(cache.get("k").is_none());
+
+        cache.put("k", &sample_result("hit"));
+        assert_eq!(cache.get("k").unwrap().explanation, Some("hit".to_string()));
+
+        cache.invalidate("k");
+        assert!(cache.get("k").is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,199 @@
+//! Background daemon mode: keeps the bridge and its warm rust-analyzer
+//! session resident and serves many requests over a local socket instead of
+//! paying process-spawn cost on every invocation.
+//!
+//! Each request/response is a 4-byte big-endian length prefix followed by
+//! that many bytes of JSON. Every accepted connection gets its own tokio
+//! task; all tasks share the single warm [`crate::analysis`] session behind
+//! its existing `Mutex`, so concurrent clients don't each spawn their own
+//! rust-analyzer.
+
+use crate::analysis::{analyze_code, apply_fixes, shutdown_session, AnalysisRequest};
+use crate::FixApplicationResult;
+use serde::{Deserialize, Serialize};
+use std::io;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, UnixListener};
+use tokio::sync::watch;
+
+/// A single framed message sent by a client.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum DaemonRequest {
+    Analyze { file_path: String, code: String },
+    /// Runs rustc's own diagnostics against `code` and applies every
+    /// machine-applicable suggestion found, returning the fixed source.
+    Fix { file_path: String, code: String },
+    Shutdown,
+}
+
+/// A single framed message sent back to a client.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum DaemonResponse {
+    Analysis(crate::analysis::AnalysisResponse),
+    Fixed(FixApplicationResult),
+    Error { message: String },
+    ShuttingDown,
+}
+
+/// Serves requests over a TCP socket at `addr` (e.g. `"127.0.0.1:7878"`)
+/// until a `shutdown` message is received.
+pub async fn serve_tcp(addr: &str) -> io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    println!("rust-analyzer-bridge daemon listening on tcp://{}", addr);
+    let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let shutdown_tx = shutdown_tx.clone();
+                let (mut reader, mut writer) = stream.into_split();
+                tokio::spawn(async move {
+                    let _ = handle_connection(&mut reader, &mut writer, &shutdown_tx).await;
+                });
+            }
+            _ = shutdown_rx.changed() => return Ok(()),
+        }
+    }
+}
+
+/// Serves requests over a Unix domain socket at `path` until a `shutdown`
+/// message is received. The socket file is removed first if it already
+/// exists (e.g. left over from a previous, uncleanly-stopped daemon).
+pub async fn serve_unix(path: &str) -> io::Result<()> {
+    let _ = std::fs::remove_file(path);
+    let listener = UnixListener::bind(path)?;
+    println!("rust-analyzer-bridge daemon listening on unix://{}", path);
+    let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let shutdown_tx = shutdown_tx.clone();
+                let (mut reader, mut writer) = stream.into_split();
+                tokio::spawn(async move {
+                    let _ = handle_connection(&mut reader, &mut writer, &shutdown_tx).await;
+                });
+            }
+            _ = shutdown_rx.changed() => return Ok(()),
+        }
+    }
+}
+
+/// Services one client connection: reads length-prefixed requests, answers
+/// each with a length-prefixed response, until the client disconnects or
+/// sends `shutdown`.
+async fn handle_connection(
+    reader: &mut (impl AsyncReadExt + Unpin),
+    writer: &mut (impl AsyncWriteExt + Unpin),
+    shutdown: &watch::Sender<bool>,
+) -> io::Result<()> {
+    loop {
+        let request: DaemonRequest = match read_frame(reader).await {
+            Ok(Some(request)) => request,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                write_frame(writer, &DaemonResponse::Error { message: e.to_string() }).await?;
+                continue;
+            }
+        };
+
+        match request {
+            DaemonRequest::Analyze { file_path, code } => {
+                let response = match analyze_code(AnalysisRequest { file_path, code }).await {
+                    Ok(result) => DaemonResponse::Analysis(result),
+                    Err(message) => DaemonResponse::Error { message },
+                };
+                write_frame(writer, &response).await?;
+            }
+            DaemonRequest::Fix { file_path, code } => {
+                let response = match apply_fixes(AnalysisRequest { file_path, code }).await {
+                    Ok(result) => DaemonResponse::Fixed(result),
+                    Err(message) => DaemonResponse::Error { message },
+                };
+                write_frame(writer, &response).await?;
+            }
+            DaemonRequest::Shutdown => {
+                write_frame(writer, &DaemonResponse::ShuttingDown).await?;
+                shutdown_session().await;
+                let _ = shutdown.send(true);
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Reads one length-prefixed JSON frame, or `None` on a clean EOF between
+/// messages.
+async fn read_frame<T: for<'de> Deserialize<'de>>(
+    reader: &mut (impl AsyncReadExt + Unpin),
+) -> io::Result<Option<T>> {
+    let mut len_bytes = [0u8; 4];
+    match reader.read_exact(&mut len_bytes).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes(len_bytes) as usize;
+
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body).await?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Writes one length-prefixed JSON frame.
+async fn write_frame<T: Serialize>(writer: &mut (impl AsyncWriteExt + Unpin), value: &T) -> io::Result<()> {
+    let body = serde_json::to_vec(value)?;
+    writer.write_all(&(body.len() as u32).to_be_bytes()).await?;
+    writer.write_all(&body).await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Frame {
+        message: String,
+    }
+
+    #[tokio::test]
+    async fn write_frame_then_read_frame_roundtrips() {
+        let mut buffer = Vec::new();
+        write_frame(
+            &mut buffer,
+            &Frame { message: "hello".to_string() },
+        )
+        .await
+        .unwrap();
+
+        let mut cursor = buffer.as_slice();
+        let frame: Option<Frame> = read_frame(&mut cursor).await.unwrap();
+        assert_eq!(frame, Some(Frame { message: "hello".to_string() }));
+    }
+
+    #[tokio::test]
+    async fn read_frame_returns_none_on_clean_eof() {
+        let mut cursor: &[u8] = &[];
+        let frame: Option<Frame> = read_frame(&mut cursor).await.unwrap();
+        assert_eq!(frame, None);
+    }
+
+    #[tokio::test]
+    async fn read_frame_rejects_invalid_json() {
+        let mut buffer = Vec::new();
+        let body = b"not json";
+        buffer.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        buffer.extend_from_slice(body);
+
+        let mut cursor = buffer.as_slice();
+        let result: io::Result<Option<Frame>> = read_frame(&mut cursor).await;
+        assert!(result.is_err());
+    }
+}
@@ -4,19 +4,46 @@
 //! through external process communication instead of direct library integration.
 
 use serde::{Deserialize, Serialize};
-use std::process::{Command, Stdio};
-use std::path::PathBuf;
+use serde_json::Value;
+use std::process::Command;
 use std::fs;
 use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+mod cache;
+pub mod daemon;
+mod lsp;
+mod types;
+
+pub use types::{
+    Applicability, AnalysisRequest, AnalysisResponse, ByteOffsets, Diagnostic, DiagnosticSeverity,
+    Location, Position, Range, Suggestion, SymbolInfo, SymbolKind,
+};
+use types::position_to_offset;
+
+/// Alias kept for source compatibility with code written against the
+/// pre-consolidation name; `AnalysisResponse` is the canonical type.
+pub type AnalysisResult = AnalysisResponse;
 
 /// Configuration for the Rust Analyzer connection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RustAnalyzerConfig {
     /// Path to the rust-analyzer executable
     pub executable_path: String,
-    
+
     /// Working directory for rust-analyzer
     pub working_dir: Option<String>,
+
+    /// Number of analysis results to keep in the in-memory cache. Ignored
+    /// when `cache_dir` is set. Defaults to 64.
+    pub cache_capacity: Option<usize>,
+
+    /// Directory to use for an on-disk result cache instead of the default
+    /// in-memory one.
+    pub cache_dir: Option<String>,
 }
 
 impl Default for RustAnalyzerConfig {
@@ -24,185 +51,512 @@ impl Default for RustAnalyzerConfig {
         Self {
             executable_path: "rust-analyzer".to_string(),
             working_dir: None,
+            cache_capacity: None,
+            cache_dir: None,
         }
     }
 }
 
-/// Diagnostic severity levels
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum DiagnosticSeverity {
-    Error,
-    Warning,
-    Information,
-    Hint,
+fn applicability_from_str(value: Option<&str>) -> Applicability {
+    match value {
+        Some("MachineApplicable") => Applicability::MachineApplicable,
+        Some("MaybeIncorrect") => Applicability::MaybeIncorrect,
+        Some("HasPlaceholders") => Applicability::HasPlaceholders,
+        _ => Applicability::Unspecified,
+    }
 }
 
-/// Position in a document
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Position {
-    pub line: u32,
-    pub character: u32,
+/// A single line of `cargo check --message-format=json` output.
+///
+/// We only care about `compiler-message` entries; `build-script-executed`,
+/// `build-finished`, etc. are ignored by `serde(other)` falling through to
+/// `Other`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum CargoMessage {
+    CompilerMessage { message: RustcDiagnostic },
+    #[serde(other)]
+    Other,
 }
 
-/// Range in a document
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Range {
-    pub start: Position,
-    pub end: Position,
+/// A rustc/cargo JSON diagnostic, as documented in
+/// `rustc --error-format=json` / `cargo check --message-format=json`.
+#[derive(Debug, Deserialize)]
+struct RustcDiagnostic {
+    message: String,
+    code: Option<RustcDiagnosticCode>,
+    level: String,
+    spans: Vec<RustcSpan>,
+    children: Vec<RustcDiagnostic>,
 }
 
-/// Diagnostic information
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Diagnostic {
-    pub message: String,
-    pub severity: DiagnosticSeverity,
-    pub range: Option<Range>,
-    pub code: Option<String>,
-    pub source: Option<String>,
+#[derive(Debug, Deserialize)]
+struct RustcDiagnosticCode {
+    code: String,
+    #[allow(dead_code)]
+    explanation: Option<String>,
 }
 
-/// Suggestion for code improvement
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Suggestion {
-    pub title: String,
-    pub description: Option<String>,
-    pub code: String,
-    pub range: Option<Range>,
+#[derive(Debug, Deserialize)]
+struct RustcSpan {
+    #[allow(dead_code)]
+    file_name: String,
+    line_start: u32,
+    line_end: u32,
+    column_start: u32,
+    column_end: u32,
+    is_primary: bool,
+    suggested_replacement: Option<String>,
+    suggestion_applicability: Option<String>,
+}
+
+impl RustcSpan {
+    /// Converts the span's 1-based line/column into our 0-based `Range`,
+    /// with `byte_offsets` resolved against `code` (the source the span was
+    /// reported against).
+    fn to_range(&self, code: &str) -> Range {
+        Range::with_byte_offsets(
+            Position {
+                line: self.line_start.saturating_sub(1),
+                character: self.column_start.saturating_sub(1),
+            },
+            Position {
+                line: self.line_end.saturating_sub(1),
+                character: self.column_end.saturating_sub(1),
+            },
+            code,
+        )
+    }
+}
+
+/// Resolves `path` to an absolute path, for building `file://` URIs:
+/// canonicalizes it if it exists (resolving away `.`/`..`/symlinks),
+/// otherwise makes it absolute by joining it onto the process's current
+/// directory — needed for a `file_path` that's virtual source passed
+/// in-memory and never written to disk. A relative or `.`-only path would
+/// otherwise produce a URI rust-analyzer can't resolve to the intended
+/// workspace/file.
+fn absolute_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    fs::canonicalize(path).unwrap_or_else(|_| {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            env::current_dir().unwrap_or_else(|_| PathBuf::from(".")).join(path)
+        }
+    })
+}
+
+fn severity_from_level(level: &str) -> DiagnosticSeverity {
+    match level {
+        "error" | "error: internal compiler error" => DiagnosticSeverity::Error,
+        "warning" => DiagnosticSeverity::Warning,
+        "help" => DiagnosticSeverity::Hint,
+        _ => DiagnosticSeverity::Information,
+    }
+}
+
+/// Converts an LSP `Diagnostic` (as published via
+/// `textDocument/publishDiagnostics`) into our `Diagnostic` type. `code` is
+/// the document's full text, used to resolve the range's `byte_offsets`.
+fn lsp_diagnostic_to_diagnostic(raw: &Value, code: &str) -> Diagnostic {
+    let severity = match raw.get("severity").and_then(Value::as_u64) {
+        Some(1) => DiagnosticSeverity::Error,
+        Some(2) => DiagnosticSeverity::Warning,
+        Some(3) => DiagnosticSeverity::Information,
+        Some(4) => DiagnosticSeverity::Hint,
+        _ => DiagnosticSeverity::Information,
+    };
+
+    Diagnostic {
+        message: raw
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        severity,
+        range: raw.get("range").map(|range| lsp_range_to_range(range, code)),
+        code: raw
+            .get("code")
+            .map(|c| c.as_str().map(str::to_string).unwrap_or_else(|| c.to_string())),
+        source: Some("rust-analyzer".to_string()),
+    }
+}
+
+/// Converts an LSP `Range` (0-based, matching ours already) into our
+/// `Range` type, resolving `byte_offsets` against `code`.
+fn lsp_range_to_range(raw: &Value, code: &str) -> Range {
+    let position = |key: &str| -> Position {
+        let p = raw.get(key);
+        Position {
+            line: p.and_then(|p| p.get("line")).and_then(Value::as_u64).unwrap_or(0) as u32,
+            character: p.and_then(|p| p.get("character")).and_then(Value::as_u64).unwrap_or(0) as u32,
+        }
+    };
+    Range::with_byte_offsets(position("start"), position("end"), code)
+}
+
+/// Maps an LSP `SymbolKind` integer onto our reduced `SymbolKind`.
+fn lsp_symbol_kind_to_symbol_kind(kind: Option<u64>) -> SymbolKind {
+    match kind {
+        Some(2) | Some(3) => SymbolKind::Module,   // Namespace, Module
+        Some(6) | Some(9) | Some(12) => SymbolKind::Function, // Method, Constructor, Function
+        Some(10) => SymbolKind::Enum,
+        Some(11) => SymbolKind::Trait, // Interface
+        Some(13) => SymbolKind::Static, // Variable
+        Some(14) => SymbolKind::Const,
+        Some(23) => SymbolKind::Struct,
+        _ => SymbolKind::Type,
+    }
+}
+
+/// Converts the array returned by `textDocument/documentSymbol` (either the
+/// hierarchical `DocumentSymbol` shape or the flat `SymbolInformation` one)
+/// into our `SymbolInfo` list.
+fn lsp_document_symbols_to_symbols(raw: &[Value], uri: &str, code: &str) -> Vec<SymbolInfo> {
+    let mut symbols = Vec::new();
+    for value in raw {
+        collect_symbol(value, uri, code, &mut symbols);
+    }
+    symbols
+}
+
+fn collect_symbol(value: &Value, uri: &str, code: &str, out: &mut Vec<SymbolInfo>) {
+    let name = value.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+    let kind = lsp_symbol_kind_to_symbol_kind(value.get("kind").and_then(Value::as_u64));
+
+    // `DocumentSymbol` nests its range directly; `SymbolInformation` nests
+    // it inside `location`.
+    let (location_uri, range) = match value.get("location") {
+        Some(location) => (
+            location.get("uri").and_then(Value::as_str).unwrap_or(uri).to_string(),
+            location.get("range").map(|range| lsp_range_to_range(range, code)),
+        ),
+        None => (uri.to_string(), value.get("range").map(|range| lsp_range_to_range(range, code))),
+    };
+
+    if let Some(range) = range {
+        out.push(SymbolInfo {
+            name,
+            kind,
+            location: Location { uri: location_uri, range },
+        });
+    }
+
+    if let Some(children) = value.get("children").and_then(Value::as_array) {
+        for child in children {
+            collect_symbol(child, uri, code, out);
+        }
+    }
+}
+
+/// Converts an LSP `CodeAction` (or the `edit`/`changes` it carries) into
+/// zero or more `Suggestion`s, resolving each range's `byte_offsets` against
+/// `code`.
+fn code_action_to_suggestions(action: &Value, code: &str) -> Vec<Suggestion> {
+    let title = action
+        .get("title")
+        .and_then(Value::as_str)
+        .unwrap_or("Suggested fix")
+        .to_string();
+
+    // rust-analyzer doesn't report rustc-style applicability, but a code
+    // action it flags as preferred is the closest equivalent to
+    // `MachineApplicable`.
+    let applicability = if action.get("isPreferred").and_then(Value::as_bool) == Some(true) {
+        Applicability::MachineApplicable
+    } else {
+        Applicability::Unspecified
+    };
+
+    let changes = action
+        .get("edit")
+        .and_then(|edit| edit.get("changes"))
+        .and_then(Value::as_object);
+
+    let Some(changes) = changes else {
+        return Vec::new();
+    };
+
+    changes
+        .values()
+        .filter_map(Value::as_array)
+        .flatten()
+        .map(|edit| Suggestion {
+            title: title.clone(),
+            description: action.get("kind").and_then(Value::as_str).map(str::to_string),
+            new_text: edit
+                .get("newText")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            range: edit.get("range").map(|range| lsp_range_to_range(range, code)),
+            applicability: applicability.clone(),
+        })
+        .collect()
 }
 
-/// Initializes communication with an external rust-analyzer process
-/// 
-/// Returns a handle to interact with the rust-analyzer process
+/// Initializes communication with rust-analyzer and launches a warm LSP
+/// server session that is reused by every subsequent analysis.
+///
+/// Returns a handle to interact with the rust-analyzer process.
 pub async fn initialize(config: RustAnalyzerConfig) -> Result<RustAnalyzer, String> {
-    // Verify that the rust-analyzer executable exists
-    if !Command::new(&config.executable_path)
+    // Verify that the rust-analyzer executable exists, and capture its
+    // version string so the result cache can key on the toolchain in use.
+    let version_output = Command::new(&config.executable_path)
         .arg("--version")
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .map_err(|e| format!("Failed to execute rust-analyzer: {}", e))?
-        .success() {
+        .output()
+        .map_err(|e| format!("Failed to execute rust-analyzer: {}", e))?;
+    if !version_output.status.success() {
         return Err(format!("rust-analyzer executable not found at: {}", config.executable_path));
     }
-    
+    let tool_version = String::from_utf8_lossy(&version_output.stdout).trim().to_string();
+
+    let root_dir = config
+        .working_dir
+        .clone()
+        .unwrap_or_else(|| ".".to_string());
+    let root_uri = format!("file://{}", absolute_path(&root_dir).display());
+
+    let session = lsp::LspClient::spawn(&config.executable_path, &root_uri)
+        .await
+        .map_err(|e| format!("Failed to start rust-analyzer server: {}", e))?;
+
+    let result_cache: Arc<dyn cache::AnalysisCache> = match &config.cache_dir {
+        Some(dir) => Arc::new(cache::DiskCache::new(dir.clone())),
+        None => Arc::new(cache::InMemoryCache::new(config.cache_capacity.unwrap_or(64))),
+    };
+
     println!("Rust Analyzer Bridge initialized with config: {:?}", config);
-    Ok(RustAnalyzer { config })
+    Ok(RustAnalyzer {
+        config,
+        tool_version,
+        session: Mutex::new(session),
+        cache: result_cache,
+    })
 }
 
 /// Rust Analyzer Client
+///
+/// Holds the warm `rust-analyzer` server process (and its request-id
+/// counter, tracked inside `LspClient`) so repeated analyses reuse one
+/// session instead of respawning rust-analyzer per call, plus a
+/// content-addressed cache of past results.
 pub struct RustAnalyzer {
     config: RustAnalyzerConfig,
+    tool_version: String,
+    session: Mutex<lsp::LspClient>,
+    cache: Arc<dyn cache::AnalysisCache>,
 }
 
 impl RustAnalyzer {
-    /// Analyze Rust code and return diagnostics
+    /// Analyze Rust code and return diagnostics.
+    ///
+    /// Looks up `code` under `file_path` in the result cache first, keyed on
+    /// both plus the active config and rust-analyzer version; on a miss it
+    /// opens `code` as `file_path` on the warm rust-analyzer session, waits
+    /// for it to publish diagnostics, asks for code actions (quick fixes)
+    /// against each one, and stores the result before returning it.
     pub async fn analyze_code(&self, file_path: &str, code: &str) -> Result<AnalysisResult, String> {
-        // Create a temporary file with the code
-        let temp_dir = env::temp_dir();
-        let path_buf = PathBuf::from(file_path);
-        let file_name = path_buf.file_name()
-            .ok_or_else(|| "Invalid file path".to_string())?;
-        let temp_file_path = temp_dir.join(file_name);
-        
-        fs::write(&temp_file_path, code)
-            .map_err(|e| format!("Failed to write temporary file: {}", e))?;
-        
-        // Run rust-analyzer in check mode
-        let output = Command::new(&self.config.executable_path)
-            .arg("--check")
-            .arg(&temp_file_path)
-            .output()
-            .map_err(|e| format!("Failed to run rust-analyzer: {}", e))?;
-        
-        // Clean up the temporary file
-        let _ = fs::remove_file(&temp_file_path);
-        
-        if !output.status.success() {
-            // Parse the error output
-            let error_output = String::from_utf8_lossy(&output.stderr);
-            return Ok(self.parse_diagnostics(error_output.to_string()));
+        let key = cache::cache_key(file_path, code, &self.config, &self.tool_version);
+        if let Some(cached) = self.cache.get(&key) {
+            return Ok(cached);
         }
-        
-        // No diagnostics if compilation succeeded
-        Ok(AnalysisResult {
-            diagnostics: vec![],
-            suggestions: vec![],
-            explanation: None,
-        })
+
+        let uri = format!("file://{}", absolute_path(file_path).display());
+        let mut session = self.session.lock().await;
+
+        session
+            .did_open(&uri, code)
+            .await
+            .map_err(|e| format!("Failed to open document with rust-analyzer: {}", e))?;
+
+        let raw_diagnostics = session
+            .wait_for_diagnostics(&uri, 50)
+            .await
+            .map_err(|e| format!("Failed to read diagnostics from rust-analyzer: {}", e))?;
+
+        let mut diagnostics = Vec::with_capacity(raw_diagnostics.len());
+        let mut suggestions = Vec::new();
+
+        for raw in &raw_diagnostics {
+            diagnostics.push(lsp_diagnostic_to_diagnostic(raw, code));
+
+            let range = raw.get("range").cloned().unwrap_or(Value::Null);
+            let actions = session
+                .code_actions(&uri, range, vec![raw.clone()])
+                .await
+                .map_err(|e| format!("Failed to fetch code actions from rust-analyzer: {}", e))?;
+            suggestions.extend(actions.iter().flat_map(|action| code_action_to_suggestions(action, code)));
+        }
+
+        let raw_symbols = session
+            .document_symbols(&uri)
+            .await
+            .map_err(|e| format!("Failed to fetch symbols from rust-analyzer: {}", e))?;
+        let symbols = lsp_document_symbols_to_symbols(&raw_symbols, &uri, code);
+
+        let result = AnalysisResult {
+            diagnostics,
+            suggestions,
+            symbols,
+            explanation: Some("Analysis performed by rust-analyzer (LSP)".to_string()),
+        };
+        self.cache.put(&key, &result);
+        Ok(result)
     }
-    
-    /// Simple parsing of diagnostic messages from rust-analyzer output
-    fn parse_diagnostics(&self, output: String) -> AnalysisResult {
-        let mut diagnostics = Vec::new();
-        
-        for line in output.lines() {
-            if line.contains("error:") || line.contains("warning:") {
-                let severity = if line.contains("error:") {
-                    DiagnosticSeverity::Error
-                } else {
-                    DiagnosticSeverity::Warning
-                };
-                
-                diagnostics.push(Diagnostic {
-                    message: line.to_string(),
-                    severity,
-                    range: None,
-                    code: None,
-                    source: Some("rust-analyzer".to_string()),
+
+    /// Clears every cached analysis result.
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Terminates the warm rust-analyzer child process.
+    pub async fn shutdown(&self) {
+        self.session.lock().await.shutdown().await;
+    }
+
+    /// Invalidates the cached result for a specific piece of code under
+    /// `file_path`, if any.
+    pub fn invalidate_cache_entry(&self, file_path: &str, code: &str) {
+        let key = cache::cache_key(file_path, code, &self.config, &self.tool_version);
+        self.cache.invalidate(&key);
+    }
+}
+
+/// Runs `cargo check --message-format=json` against `code` in a throwaway
+/// project. Unlike `RustAnalyzer::analyze_code`, this only ever shells out to
+/// `cargo` — no rust-analyzer session required — and gives us rustc's own
+/// `Applicability` for each suggestion, which rust-analyzer does not expose
+/// over LSP and which fix-applying callers rely on to decide which edits are
+/// safe to apply automatically.
+pub async fn cargo_check_diagnostics(code: &str) -> Result<AnalysisResult, String> {
+    // Per-call, not just per-process: the daemon (see `daemon.rs`) can have
+    // several `cargo_check_diagnostics` calls in flight at once, and they
+    // must not share a scratch project.
+    static NEXT_CALL_ID: AtomicU64 = AtomicU64::new(0);
+    let call_id = NEXT_CALL_ID.fetch_add(1, Ordering::Relaxed);
+    let project_dir = env::temp_dir().join(format!("rust-analyzer-bridge-{}-{}", std::process::id(), call_id));
+    let src_dir = project_dir.join("src");
+    fs::create_dir_all(&src_dir).map_err(|e| format!("Failed to create temporary project: {}", e))?;
+
+    fs::write(project_dir.join("Cargo.toml"), temp_cargo_manifest())
+        .map_err(|e| format!("Failed to write temporary Cargo.toml: {}", e))?;
+    fs::write(src_dir.join("main.rs"), code).map_err(|e| format!("Failed to write temporary file: {}", e))?;
+
+    let output = Command::new("cargo")
+        .arg("check")
+        .arg("--message-format=json")
+        .current_dir(&project_dir)
+        .output()
+        .map_err(|e| format!("Failed to run cargo check: {}", e));
+
+    let _ = fs::remove_dir_all(&project_dir);
+    let output = output?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_diagnostics(&stdout, code))
+}
+
+/// Parses the line-delimited JSON emitted by `cargo check
+/// --message-format=json` into our `AnalysisResult` type. `code` is the
+/// source the diagnostics were reported against, used to resolve each
+/// `Range`'s `byte_offsets`.
+fn parse_diagnostics(output: &str, code: &str) -> AnalysisResult {
+    let mut diagnostics = Vec::new();
+    let mut suggestions = Vec::new();
+
+    for line in output.lines() {
+        let message = match serde_json::from_str::<CargoMessage>(line) {
+            Ok(CargoMessage::CompilerMessage { message }) => message,
+            _ => continue,
+        };
+
+        let primary_span = message.spans.iter().find(|span| span.is_primary);
+        let range = primary_span.map(|span| span.to_range(code));
+
+        for span in &message.spans {
+            if let Some(new_text) = &span.suggested_replacement {
+                suggestions.push(Suggestion {
+                    title: message.message.clone(),
+                    description: message.code.as_ref().map(|c| c.code.clone()),
+                    new_text: new_text.clone(),
+                    range: Some(span.to_range(code)),
+                    applicability: applicability_from_str(span.suggestion_applicability.as_deref()),
                 });
             }
         }
-        
-        // Generate simple suggestions based on diagnostics
-        let suggestions = diagnostics.iter()
-            .filter(|d| matches!(d.severity, DiagnosticSeverity::Error))
-            .map(|d| Suggestion {
-                title: "Fix error".to_string(),
-                description: Some(format!("Suggestion to fix: {}", d.message)),
-                code: "// TODO: Implement fix".to_string(),
-                range: None,
-            })
-            .collect();
-        
-        AnalysisResult {
-            diagnostics,
-            suggestions,
-            explanation: Some("Analysis performed by rust-analyzer".to_string()),
+
+        for child in &message.children {
+            if child.level == "help" {
+                for span in &child.spans {
+                    if let Some(new_text) = &span.suggested_replacement {
+                        suggestions.push(Suggestion {
+                            title: child.message.clone(),
+                            description: None,
+                            new_text: new_text.clone(),
+                            range: Some(span.to_range(code)),
+                            applicability: applicability_from_str(span.suggestion_applicability.as_deref()),
+                        });
+                    }
+                }
+            }
         }
+
+        diagnostics.push(Diagnostic {
+            message: message.message,
+            severity: severity_from_level(&message.level),
+            range,
+            code: message.code.map(|c| c.code),
+            source: Some("rustc".to_string()),
+        });
+    }
+
+    AnalysisResult {
+        diagnostics,
+        suggestions,
+        symbols: Vec::new(),
+        explanation: Some("Analysis performed by cargo check".to_string()),
     }
 }
 
+/// Minimal manifest for the throwaway project we check the submitted code in.
+fn temp_cargo_manifest() -> &'static str {
+    "[package]\nname = \"rust-analyzer-bridge-scratch\"\nversion = \"0.0.0\"\nedition = \"2021\"\n"
+}
+
 /// Module for code analysis functionality
 pub mod analysis {
     use super::*;
-    
-    /// Simple code analysis request structure
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct AnalysisRequest {
-        pub file_path: String,
-        pub code: String,
-    }
-    
-    /// Simple code analysis response structure
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct AnalysisResponse {
-        pub diagnostics: Vec<Diagnostic>,
-        pub suggestions: Vec<Suggestion>,
-        pub explanation: Option<String>,
-    }
-    
-    /// Send code for analysis to rust-analyzer
+
+    pub use crate::types::{AnalysisRequest, AnalysisResponse};
+
+    /// Send code for analysis to rust-analyzer, reusing the warm session
+    /// from a previous call rather than respawning rust-analyzer each time.
     pub async fn analyze_code(request: AnalysisRequest) -> Result<AnalysisResponse, String> {
-        let config = RustAnalyzerConfig::default();
-        let analyzer = initialize(config).await?;
-        
-        let result = analyzer.analyze_code(&request.file_path, &request.code).await?;
-        
-        Ok(AnalysisResponse {
-            diagnostics: result.diagnostics,
-            suggestions: result.suggestions,
-            explanation: result.explanation,
-        })
+        let mut session = get_session().lock().await;
+        if session.is_none() {
+            let config = get_config()
+                .lock()
+                .unwrap()
+                .clone()
+                .unwrap_or_default();
+            *session = Some(initialize(config).await?);
+        }
+        let analyzer = session.as_ref().expect("session initialized above");
+
+        analyzer.analyze_code(&request.file_path, &request.code).await
+    }
+
+    /// Runs `cargo check` against `request.code` for rustc's own
+    /// `Applicability`, then applies every machine-applicable suggestion it
+    /// found. Unlike `analyze_code`, this never touches the warm
+    /// rust-analyzer session — `cargo_check_diagnostics` only shells out to
+    /// `cargo` — so it works even in environments with no rust-analyzer
+    /// executable available.
+    pub async fn apply_fixes(request: AnalysisRequest) -> Result<FixApplicationResult, String> {
+        let result = crate::cargo_check_diagnostics(&request.code).await?;
+        Ok(crate::apply_fixes(&request.code, &result.suggestions))
     }
 
     static CONFIG: std::sync::OnceLock<std::sync::Mutex<Option<RustAnalyzerConfig>>> = std::sync::OnceLock::new();
@@ -211,6 +565,23 @@ pub mod analysis {
         CONFIG.get_or_init(|| std::sync::Mutex::new(None))
     }
 
+    static SESSION: std::sync::OnceLock<Mutex<Option<RustAnalyzer>>> = std::sync::OnceLock::new();
+
+    /// The warm `RustAnalyzer` session shared across `analyze_code` calls.
+    fn get_session() -> &'static Mutex<Option<RustAnalyzer>> {
+        SESSION.get_or_init(|| Mutex::new(None))
+    }
+
+    /// Shuts down the warm rust-analyzer session, if one is running, killing
+    /// its child process. Used by the daemon when it's asked to shut down
+    /// gracefully, so the session isn't simply abandoned along with it.
+    pub async fn shutdown_session() {
+        let mut session = get_session().lock().await;
+        if let Some(analyzer) = session.take() {
+            analyzer.shutdown().await;
+        }
+    }
+
     /// Set configuration for the analysis module
     pub fn set_config(config: String) -> Result<(), String> {
         // Parse the config string into RustAnalyzerConfig
@@ -223,12 +594,67 @@ pub mod analysis {
     }
 }
 
-/// Analysis result from rust-analyzer
+/// Result of applying every machine-applicable suggestion to a piece of
+/// source code, e.g. for a one-click "fix all safe problems" action.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AnalysisResult {
-    pub diagnostics: Vec<Diagnostic>,
-    pub suggestions: Vec<Suggestion>,
-    pub explanation: Option<String>,
+pub struct FixApplicationResult {
+    pub fixed_code: String,
+    pub applied: usize,
+    /// Machine-applicable suggestions that were skipped because their range
+    /// overlapped one already applied.
+    pub skipped_overlapping: Vec<Suggestion>,
+}
+
+/// Applies every `MachineApplicable` suggestion to `code`, modeled on
+/// `cargo fix`: edits are sorted by start offset and applied from the end of
+/// the file backward so earlier ranges stay valid. Two suggestions whose
+/// ranges overlap can't both be applied; the later one (by start offset) is
+/// skipped and reported in `skipped_overlapping`.
+pub fn apply_fixes(code: &str, suggestions: &[Suggestion]) -> FixApplicationResult {
+    let mut edits: Vec<&Suggestion> = suggestions
+        .iter()
+        .filter(|s| s.applicability == Applicability::MachineApplicable && s.range.is_some())
+        .collect();
+    edits.sort_by_key(|s| position_to_offset(code, &s.range.as_ref().expect("filtered above").start));
+
+    // Of two overlapping edits, keep the earlier one (by start offset) and
+    // skip the later one.
+    let mut skipped_overlapping = Vec::new();
+    let mut kept_end = 0;
+    edits.retain(|s| {
+        let range = s.range.as_ref().expect("filtered above");
+        if position_to_offset(code, &range.start) < kept_end {
+            skipped_overlapping.push((*s).clone());
+            return false;
+        }
+        kept_end = position_to_offset(code, &range.end);
+        true
+    });
+
+    let mut fixed_code = code.to_string();
+    let mut applied = 0;
+    let mut earliest_applied_offset = fixed_code.len();
+
+    for suggestion in edits.into_iter().rev() {
+        let range = suggestion.range.as_ref().expect("filtered above");
+        let start = position_to_offset(&fixed_code, &range.start);
+        let end = position_to_offset(&fixed_code, &range.end);
+
+        if end > earliest_applied_offset {
+            skipped_overlapping.push(suggestion.clone());
+            continue;
+        }
+
+        fixed_code.replace_range(start..end, &suggestion.new_text);
+        earliest_applied_offset = start;
+        applied += 1;
+    }
+
+    FixApplicationResult {
+        fixed_code,
+        applied,
+        skipped_overlapping,
+    }
 }
 
 #[cfg(test)]
@@ -241,4 +667,88 @@ mod tests {
         assert_eq!(config.executable_path, "rust-analyzer");
         assert!(config.working_dir.is_none());
     }
+
+    #[test]
+    fn parse_diagnostics_reads_compiler_messages_and_ignores_other_reasons() {
+        let output = r#"
+{"reason":"compiler-artifact"}
+{"reason":"compiler-message","message":{"message":"mismatched types","code":{"code":"E0308","explanation":null},"level":"error","spans":[{"file_name":"src/main.rs","line_start":2,"line_end":2,"column_start":18,"column_end":22,"is_primary":true,"suggested_replacement":null,"suggestion_applicability":null}],"children":[{"message":"try using a conversion method","code":null,"level":"help","spans":[{"file_name":"src/main.rs","line_start":2,"line_end":2,"column_start":18,"column_end":22,"is_primary":true,"suggested_replacement":"\"42\".parse().unwrap()","suggestion_applicability":"MaybeIncorrect"}],"children":[]}]}}
+{"reason":"build-finished","success":false}
+"#;
+        let code = "fn main() {\n    let y: i32 = \"42\";\n}\n";
+
+        let result = parse_diagnostics(output, code);
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].message, "mismatched types");
+        assert_eq!(result.diagnostics[0].code, Some("E0308".to_string()));
+        assert!(matches!(result.diagnostics[0].severity, DiagnosticSeverity::Error));
+        let byte_offsets = result.diagnostics[0]
+            .range
+            .as_ref()
+            .and_then(|r| r.byte_offsets.as_ref())
+            .expect("diagnostic range should carry byte offsets");
+        assert_eq!((byte_offsets.start, byte_offsets.end), (29, 33));
+
+        assert_eq!(result.suggestions.len(), 1);
+        assert_eq!(result.suggestions[0].new_text, "\"42\".parse().unwrap()");
+        assert_eq!(result.suggestions[0].applicability, Applicability::MaybeIncorrect);
+    }
+
+    #[test]
+    fn parse_diagnostics_on_empty_output_returns_no_diagnostics() {
+        let result = parse_diagnostics("", "");
+        assert!(result.diagnostics.is_empty());
+        assert!(result.suggestions.is_empty());
+    }
+
+    fn suggestion(line: u32, start: u32, end: u32, new_text: &str, applicability: Applicability) -> Suggestion {
+        Suggestion {
+            title: "fix".to_string(),
+            description: None,
+            new_text: new_text.to_string(),
+            range: Some(Range::new(
+                Position { line, character: start },
+                Position { line, character: end },
+            )),
+            applicability,
+        }
+    }
+
+    #[test]
+    fn apply_fixes_applies_machine_applicable_edits_back_to_front() {
+        let code = "fn main() {\n    let y: i32 = \"42\";\n}\n";
+        let suggestions = vec![suggestion(1, 17, 21, "42", Applicability::MachineApplicable)];
+
+        let result = apply_fixes(code, &suggestions);
+
+        assert_eq!(result.applied, 1);
+        assert!(result.skipped_overlapping.is_empty());
+        assert_eq!(result.fixed_code, "fn main() {\n    let y: i32 = 42;\n}\n");
+    }
+
+    #[test]
+    fn apply_fixes_skips_overlapping_edits() {
+        let code = "let y: i32 = \"42\";";
+        let suggestions = vec![
+            suggestion(0, 14, 18, "42", Applicability::MachineApplicable),
+            suggestion(0, 13, 18, "0", Applicability::MachineApplicable),
+        ];
+
+        let result = apply_fixes(code, &suggestions);
+
+        assert_eq!(result.applied, 1);
+        assert_eq!(result.skipped_overlapping.len(), 1);
+    }
+
+    #[test]
+    fn apply_fixes_ignores_non_machine_applicable_suggestions() {
+        let code = "let y: i32 = \"42\";";
+        let suggestions = vec![suggestion(0, 14, 18, "42", Applicability::MaybeIncorrect)];
+
+        let result = apply_fixes(code, &suggestions);
+
+        assert_eq!(result.applied, 0);
+        assert_eq!(result.fixed_code, code);
+    }
 }
@@ -0,0 +1,283 @@
+//! Minimal LSP/JSON-RPC client for talking to a long-lived `rust-analyzer`
+//! server process.
+//!
+//! This only implements the handful of requests/notifications the bridge
+//! needs (`initialize`, `initialized`, `textDocument/didOpen`,
+//! `textDocument/publishDiagnostics`, `textDocument/codeAction`,
+//! `textDocument/documentSymbol`) rather than
+//! the full LSP surface.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+
+/// A running `rust-analyzer` process, launched with no arguments (its
+/// default, LSP-speaking server mode), with an open JSON-RPC channel over
+/// stdio.
+pub struct LspClient {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: i64,
+    /// Diagnostics most recently published for each file URI, keyed as sent
+    /// by the server via `textDocument/publishDiagnostics`.
+    diagnostics_by_uri: HashMap<String, Vec<Value>>,
+}
+
+impl LspClient {
+    /// Launches `rust-analyzer` in LSP server mode and performs the
+    /// `initialize`/`initialized` handshake against `root_uri`.
+    pub async fn spawn(executable_path: &str, root_uri: &str) -> io::Result<Self> {
+        let mut child = tokio::process::Command::new(executable_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| io::Error::other("missing child stdin"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| io::Error::other("missing child stdout"))?;
+
+        let mut client = Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            next_id: 1,
+            diagnostics_by_uri: HashMap::new(),
+        };
+
+        client
+            .request(
+                "initialize",
+                json!({
+                    "processId": std::process::id(),
+                    "rootUri": root_uri,
+                    "capabilities": {
+                        "textDocument": {
+                            "publishDiagnostics": { "relatedInformation": true },
+                            "codeAction": { "dynamicRegistration": false },
+                        }
+                    },
+                }),
+            )
+            .await?;
+        client.notify("initialized", json!({})).await?;
+
+        Ok(client)
+    }
+
+    /// Sends `textDocument/didOpen` for `uri` with `text` as the full
+    /// document contents.
+    pub async fn did_open(&mut self, uri: &str, text: &str) -> io::Result<()> {
+        self.diagnostics_by_uri.remove(uri);
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": {
+                    "uri": uri,
+                    "languageId": "rust",
+                    "version": 1,
+                    "text": text,
+                }
+            }),
+        )
+        .await
+    }
+
+    /// Waits (draining incoming notifications) until the server has
+    /// published diagnostics for `uri`, or `max_messages` have been read
+    /// without seeing one.
+    ///
+    /// Returns an `ErrorKind::TimedOut` error rather than an empty `Vec` if
+    /// the server never publishes for `uri` within `max_messages` — a file
+    /// rust-analyzer has actually finished with always gets a
+    /// `publishDiagnostics`, even an empty one, so silently returning `Vec`
+    /// here would make slow initial indexing indistinguishable from "no
+    /// diagnostics".
+    pub async fn wait_for_diagnostics(&mut self, uri: &str, max_messages: usize) -> io::Result<Vec<Value>> {
+        for _ in 0..max_messages {
+            if let Some(diagnostics) = self.diagnostics_by_uri.get(uri) {
+                return Ok(diagnostics.clone());
+            }
+            self.read_notification_or_response().await?;
+        }
+        self.diagnostics_by_uri.get(uri).cloned().map(Ok).unwrap_or_else(|| {
+            Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!(
+                    "rust-analyzer did not publish diagnostics for {} within {} messages",
+                    uri, max_messages
+                ),
+            ))
+        })
+    }
+
+    /// Issues `textDocument/codeAction` over `range` and returns the raw
+    /// `CodeAction`/`Command` values the server replies with.
+    pub async fn code_actions(&mut self, uri: &str, range: Value, diagnostics: Vec<Value>) -> io::Result<Vec<Value>> {
+        let result = self
+            .request(
+                "textDocument/codeAction",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "range": range,
+                    "context": { "diagnostics": diagnostics },
+                }),
+            )
+            .await?;
+
+        Ok(result.as_array().cloned().unwrap_or_default())
+    }
+
+    /// Issues `textDocument/documentSymbol` for `uri` and returns the raw
+    /// `DocumentSymbol`/`SymbolInformation` values the server replies with.
+    pub async fn document_symbols(&mut self, uri: &str) -> io::Result<Vec<Value>> {
+        let result = self
+            .request(
+                "textDocument/documentSymbol",
+                json!({ "textDocument": { "uri": uri } }),
+            )
+            .await?;
+
+        Ok(result.as_array().cloned().unwrap_or_default())
+    }
+
+    /// Sends a request, then reads messages until the matching response
+    /// arrives, stashing any `publishDiagnostics` notifications seen along
+    /// the way.
+    async fn request(&mut self, method: &str, params: Value) -> io::Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.write_message(&json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))
+        .await?;
+
+        loop {
+            let message = self.read_message().await?;
+            if message.get("id") == Some(&json!(id)) && message.get("method").is_none() {
+                if let Some(error) = message.get("error") {
+                    return Err(io::Error::other(format!(
+                        "rust-analyzer returned an error for {}: {}",
+                        method, error
+                    )));
+                }
+                return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+            }
+            self.handle_server_message(message).await?;
+        }
+    }
+
+    /// Sends a notification (no response expected).
+    async fn notify(&mut self, method: &str, params: Value) -> io::Result<()> {
+        self.write_message(&json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        }))
+        .await
+    }
+
+    /// Reads a single message and passes it to `handle_server_message`,
+    /// which records `publishDiagnostics`, answers server requests, and
+    /// discards anything else.
+    async fn read_notification_or_response(&mut self) -> io::Result<()> {
+        let message = self.read_message().await?;
+        self.handle_server_message(message).await
+    }
+
+    /// Handles one message the server sent us that wasn't the response we
+    /// were waiting for: records `publishDiagnostics`, and answers any other
+    /// server-initiated *request* (it carries both `method` and `id`, e.g.
+    /// `workspace/configuration` or `client/registerCapability`) with a
+    /// stub result so the server isn't left blocked forever waiting on a
+    /// reply we'd otherwise never send. Notifications we don't otherwise
+    /// care about are simply dropped, matching JSON-RPC's "don't care about
+    /// the reply" semantics.
+    async fn handle_server_message(&mut self, message: Value) -> io::Result<()> {
+        if message.get("method") == Some(&json!("textDocument/publishDiagnostics")) {
+            if let Some(params) = message.get("params") {
+                let uri = params
+                    .get("uri")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let diagnostics = params
+                    .get("diagnostics")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                self.diagnostics_by_uri.insert(uri, diagnostics);
+            }
+            return Ok(());
+        }
+
+        if let (Some(_method), Some(id)) = (message.get("method"), message.get("id")) {
+            let result = match message.get("params").and_then(|p| p.get("items")).and_then(Value::as_array) {
+                Some(items) => Value::Array(vec![Value::Null; items.len()]),
+                None => Value::Null,
+            };
+            self.write_message(&json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "result": result,
+            }))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a `Content-Length`-framed JSON-RPC message.
+    async fn write_message(&mut self, value: &Value) -> io::Result<()> {
+        let body = serde_json::to_vec(value)?;
+        self.stdin
+            .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+            .await?;
+        self.stdin.write_all(&body).await?;
+        self.stdin.flush().await
+    }
+
+    /// Reads one `Content-Length`-framed JSON-RPC message.
+    async fn read_message(&mut self) -> io::Result<Value> {
+        let mut content_length = None;
+        loop {
+            let mut header = String::new();
+            if self.stdout.read_line(&mut header).await? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "rust-analyzer closed stdout"));
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length: ") {
+                content_length = Some(value.trim().parse::<usize>().map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData, format!("bad Content-Length: {}", e))
+                })?);
+            }
+        }
+
+        let content_length =
+            content_length.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header"))?;
+        let mut body = vec![0u8; content_length];
+        self.stdout.read_exact(&mut body).await?;
+        serde_json::from_slice(&body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Terminates the rust-analyzer child process.
+    pub async fn shutdown(&mut self) {
+        let _ = self.child.kill().await;
+    }
+}
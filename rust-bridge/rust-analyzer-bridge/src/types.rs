@@ -0,0 +1,252 @@
+//! The canonical request/response/diagnostic types for the bridge.
+//!
+//! Every entry point — the `analyze` binaries (including the `serve`
+//! subcommand), the `analysis`
+//! module, and the daemon's socket protocol — serializes and deserializes
+//! these same types, so a request built by one can be read by another.
+
+use serde::{Deserialize, Serialize};
+
+/// Position in a document (0-based line/character, matching LSP).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+/// Range in a document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+    /// Absolute byte offsets for `start`/`end`, when known. Kept alongside
+    /// the line/character positions rather than replacing them, since not
+    /// every producer (e.g. raw LSP responses) has the source text handy to
+    /// compute them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_offsets: Option<ByteOffsets>,
+}
+
+impl Range {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end, byte_offsets: None }
+    }
+
+    /// Like [`Range::new`], but also populates `byte_offsets` by resolving
+    /// `start`/`end` against `text`, the full source they're positions into.
+    pub fn with_byte_offsets(start: Position, end: Position, text: &str) -> Self {
+        let byte_offsets = Some(ByteOffsets {
+            start: position_to_offset(text, &start) as u64,
+            end: position_to_offset(text, &end) as u64,
+        });
+        Self { start, end, byte_offsets }
+    }
+}
+
+/// Converts a 0-based line/character `Position` into a byte offset into
+/// `text`.
+///
+/// Clamped rather than panicking on an out-of-range `position`: a `line`
+/// past the end of `text` resolves to `text.len()`, and a `character` past
+/// the end of its line resolves to that line's length. Callers may see a
+/// `Position` that doesn't line up with the `text` they're resolving it
+/// against — e.g. a suggestion computed against stale cached code, or an
+/// upstream LSP response that's just wrong — and a clamped, in-bounds
+/// offset is a safer result than slicing past the end of the string.
+pub(crate) fn position_to_offset(text: &str, position: &Position) -> usize {
+    let mut offset = 0;
+    for (i, line) in text.split('\n').enumerate() {
+        if i as u32 == position.line {
+            let char_offset: usize = line
+                .chars()
+                .take(position.character as usize)
+                .map(char::len_utf8)
+                .sum();
+            return (offset + char_offset).min(text.len());
+        }
+        offset += line.len() + 1;
+    }
+    text.len()
+}
+
+/// A `Range`'s start/end as absolute byte offsets into the source.
+///
+/// Serialized as strings (via [`byte_offset`]) rather than JSON numbers:
+/// JS's `Number` only represents integers exactly up to 2^53 - 1, and a byte
+/// offset into a large generated file could exceed that.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ByteOffsets {
+    #[serde(with = "byte_offset")]
+    pub start: u64,
+    #[serde(with = "byte_offset")]
+    pub end: u64,
+}
+
+/// A `serde(with = ...)` adapter that (de)serializes a `u64` as a JSON
+/// string, for fields that can exceed JS's safe-integer range.
+pub mod byte_offset {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Diagnostic severity levels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+/// Diagnostic information.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub message: String,
+    pub severity: DiagnosticSeverity,
+    pub range: Option<Range>,
+    pub code: Option<String>,
+    pub source: Option<String>,
+}
+
+/// How safe a suggestion is to apply automatically, mirroring rustc's own
+/// `Applicability` enum.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended; safe to apply
+    /// without review.
+    MachineApplicable,
+    /// The suggestion may or may not be what the user intended.
+    MaybeIncorrect,
+    /// The suggestion contains placeholders like `/* ... */` and can't be
+    /// applied mechanically.
+    HasPlaceholders,
+    /// No applicability information is available.
+    Unspecified,
+}
+
+/// Suggestion for code improvement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub title: String,
+    pub description: Option<String>,
+    pub new_text: String,
+    pub range: Option<Range>,
+    pub applicability: Applicability,
+}
+
+/// The kind of a symbol reported by [`SymbolInfo`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SymbolKind {
+    Function,
+    Struct,
+    Enum,
+    Trait,
+    Module,
+    Type,
+    Const,
+    Static,
+    Macro,
+}
+
+/// Where a symbol is defined.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Location {
+    pub uri: String,
+    pub range: Range,
+}
+
+/// A named symbol (function, struct, ...) found while analyzing code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolInfo {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub location: Location,
+}
+
+/// Request to analyze a piece of Rust code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisRequest {
+    pub file_path: String,
+    pub code: String,
+}
+
+/// Result of analyzing a piece of Rust code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisResponse {
+    pub diagnostics: Vec<Diagnostic>,
+    pub suggestions: Vec<Suggestion>,
+    #[serde(default)]
+    pub symbols: Vec<SymbolInfo>,
+    pub explanation: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_offsets_serialize_as_strings() {
+        let offsets = ByteOffsets { start: 0, end: 9_007_199_254_740_993 };
+        let json = serde_json::to_value(&offsets).unwrap();
+        assert_eq!(json["start"], serde_json::json!("0"));
+        assert_eq!(json["end"], serde_json::json!("9007199254740993"));
+    }
+
+    #[test]
+    fn byte_offsets_roundtrip_through_json() {
+        let offsets = ByteOffsets { start: 29, end: 33 };
+        let json = serde_json::to_string(&offsets).unwrap();
+        let back: ByteOffsets = serde_json::from_str(&json).unwrap();
+        assert_eq!((back.start, back.end), (29, 33));
+    }
+
+    #[test]
+    fn range_new_leaves_byte_offsets_unset() {
+        let range = Range::new(Position { line: 0, character: 0 }, Position { line: 0, character: 1 });
+        assert!(range.byte_offsets.is_none());
+    }
+
+    #[test]
+    fn range_with_byte_offsets_resolves_against_text() {
+        let text = "fn main() {\n    let y = 1;\n}\n";
+        let range = Range::with_byte_offsets(
+            Position { line: 1, character: 8 },
+            Position { line: 1, character: 9 },
+            text,
+        );
+        let offsets = range.byte_offsets.expect("byte_offsets should be set");
+        assert_eq!((offsets.start, offsets.end), (20, 21));
+    }
+
+    #[test]
+    fn position_to_offset_clamps_line_past_end_of_text() {
+        let text = "ab";
+        assert_eq!(position_to_offset(text, &Position { line: 5, character: 0 }), text.len());
+    }
+
+    #[test]
+    fn position_to_offset_clamps_character_past_end_of_line() {
+        let text = "ab\ncd";
+        assert_eq!(position_to_offset(text, &Position { line: 0, character: 99 }), 2);
+    }
+
+    #[test]
+    fn range_with_byte_offsets_does_not_panic_on_out_of_range_position() {
+        let range = Range::with_byte_offsets(
+            Position { line: 9, character: 0 },
+            Position { line: 9, character: 5 },
+            "ab",
+        );
+        let offsets = range.byte_offsets.expect("byte_offsets should be set");
+        assert_eq!((offsets.start, offsets.end), (2, 2));
+    }
+}